@@ -1,96 +1,530 @@
-#[derive(Debug, Clone)]
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use serde::{Deserialize, Serialize};
+use serde_big_array::BigArray;
+use std::collections::HashMap;
+use std::path::Path;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Blockchain {
     chain: Vec<Block>,
     pending: Vec<Transaction>,
     nonce: u32,
 }
 
+impl Default for Blockchain {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
 impl Blockchain {
     pub fn new() -> Self {
-        Blockchain {
-            chain: vec![Block::genesis()],
+        Self::with_genesis_balances(&[]).expect("empty genesis balances are always valid")
+    }
+
+    /// Creates a chain whose genesis block mints `amount` to each `account`,
+    /// so balances can start non-zero without an unsigned out-of-band credit.
+    /// Rejects a non-positive `amount` the same way a regular transfer would.
+    pub fn with_genesis_balances(balances: &[([u8; 32], i64)]) -> Result<Self, TxError> {
+        if balances.iter().any(|(_, amount)| *amount <= 0) {
+            return Err(TxError::InvalidAmount);
+        }
+        Ok(Blockchain {
+            chain: vec![Block::genesis(balances)],
             pending: Vec::new(),
             nonce: 0,
-        }
+        })
     }
 
     pub fn current_block(&self) -> &Block {
         &self.chain[self.chain.len() - 1]
     }
 
-    pub fn submit_tx(&mut self, payload: String) {
-        let tx = Transaction::new(payload, self.nonce);
+    pub fn submit_tx(&mut self, payload: String, signing_key: &SigningKey) -> Result<(), TxError> {
+        let tx = Transaction::new(Payload::Raw(payload), self.nonce, signing_key);
+        self.submit_signed_tx(tx)
+    }
+
+    /// Submits a transfer of `amount` from the key's account to `to`. The
+    /// amount and signature are checked immediately; the sender's balance is
+    /// checked when the transaction is folded into a block via
+    /// [`Blockchain::new_block`].
+    pub fn submit_transfer(
+        &mut self,
+        to: [u8; 32],
+        amount: i64,
+        signing_key: &SigningKey,
+    ) -> Result<(), TxError> {
+        if amount <= 0 {
+            return Err(TxError::InvalidAmount);
+        }
+        let tx = Transaction::new(Payload::Transfer { to, amount }, self.nonce, signing_key);
+        self.submit_signed_tx(tx)
+    }
+
+    /// Accepts an already-signed transaction, rejecting it if its signature
+    /// does not validate against its claimed sender or it carries a
+    /// non-positive transfer amount.
+    pub fn submit_signed_tx(&mut self, tx: Transaction) -> Result<(), TxError> {
+        if !tx.verify() {
+            return Err(TxError::InvalidSignature);
+        }
+        if let Payload::Transfer { amount, .. } = &tx.payload {
+            if *amount <= 0 {
+                return Err(TxError::InvalidAmount);
+            }
+        }
         self.pending.push(tx);
         self.nonce += 1;
+        Ok(())
     }
 
-    pub fn new_block(&mut self) {
+    /// Sums confirmed transfers into and out of `account` across the whole
+    /// chain, including the genesis mint. Saturates rather than overflowing,
+    /// since a chain old enough to accumulate `i64::MAX` in transfers has
+    /// bigger problems than a saturated balance.
+    pub fn balance(&self, account: &[u8; 32]) -> i64 {
+        let mut total: i64 = 0;
+        for block in &self.chain {
+            for tx in &block.transactions {
+                if let Payload::Transfer { to, amount } = &tx.payload {
+                    if to == account {
+                        total = total.saturating_add(*amount);
+                    }
+                    if &tx.from == account {
+                        total = total.saturating_sub(*amount);
+                    }
+                }
+            }
+        }
+        total
+    }
+
+    /// Folds the pending pool into a new block, rejecting the whole batch if
+    /// any sender's pending transfers would spend more than their balance (or
+    /// overflow the running total of what they're trying to spend).
+    pub fn new_block(&mut self, difficulty: usize) -> Result<(), TxError> {
+        let mut spent: HashMap<[u8; 32], i64> = HashMap::new();
+        for tx in &self.pending {
+            if let Payload::Transfer { amount, .. } = &tx.payload {
+                let running = spent.entry(tx.from).or_insert(0);
+                *running = running.checked_add(*amount).ok_or(TxError::AmountOverflow)?;
+                if *running > self.balance(&tx.from) {
+                    return Err(TxError::InsufficientBalance);
+                }
+            }
+        }
+
         let pending = self.pending.to_owned();
-        let new_block = Block::new(self.current_block(), pending);
+        let new_block = Block::new(self.current_block(), pending, difficulty);
         self.chain.push(new_block);
         self.pending = Vec::new();
+        Ok(())
+    }
+
+    /// Walks the chain from genesis, checking that every block correctly
+    /// links to, and builds on, the one before it.
+    pub fn is_valid(&self) -> Result<(), ValidationError> {
+        let genesis = &self.chain[0];
+        for tx in &genesis.transactions {
+            if tx.id != Transaction::compute_id(&tx.payload, tx.nonce) {
+                return Err(ValidationError::HashMismatch { index: 0 });
+            }
+        }
+        let genesis_tx_ids: Vec<String> = genesis.transactions.iter().map(|tx| tx.id.clone()).collect();
+        if genesis.merkle_root != utils::merkle_root(&genesis_tx_ids) {
+            return Err(ValidationError::HashMismatch { index: 0 });
+        }
+        let genesis_id_str = genesis.merkle_root.clone() + "genesis";
+        if genesis.id != utils::hash_str(genesis_id_str.as_bytes()) {
+            return Err(ValidationError::HashMismatch { index: 0 });
+        }
+
+        for window in self.chain.windows(2) {
+            let (prev, block) = (&window[0], &window[1]);
+
+            if block.previous != prev.id {
+                return Err(ValidationError::BrokenLink { index: block.index });
+            }
+            if block.index != prev.index + 1 {
+                return Err(ValidationError::IndexMismatch { index: block.index });
+            }
+            if block.timestamp < prev.timestamp {
+                return Err(ValidationError::NonMonotonicTimestamp { index: block.index });
+            }
+            if block.difficulty < prev.difficulty {
+                return Err(ValidationError::DifficultyDecreased { index: block.index });
+            }
+
+            let tx_ids: Vec<String> = block.transactions.iter().map(|tx| tx.id.clone()).collect();
+            let expected_merkle_root = utils::merkle_root(&tx_ids);
+            if block.merkle_root != expected_merkle_root {
+                return Err(ValidationError::HashMismatch { index: block.index });
+            }
+
+            let preimage = block.merkle_root.clone() + &block.previous + &block.difficulty.to_string();
+            let digest = utils::hash_with_nonce(&preimage, block.nonce);
+            if utils::hex_string(&digest) != block.id {
+                return Err(ValidationError::HashMismatch { index: block.index });
+            }
+            if utils::leading_zero_bits(&digest) < block.difficulty {
+                return Err(ValidationError::InsufficientWork { index: block.index });
+            }
+
+            if block.transactions.iter().any(|tx| !tx.verify()) {
+                return Err(ValidationError::InvalidSignature { index: block.index });
+            }
+        }
+        Ok(())
+    }
+
+    /// Writes the full chain, pending pool, and nonce counter to `path` as JSON.
+    pub fn save(&self, path: impl AsRef<Path>) -> Result<(), PersistenceError> {
+        let json = serde_json::to_string_pretty(self)?;
+        std::fs::write(path, json)?;
+        Ok(())
+    }
+
+    /// Reads a chain previously written by [`Blockchain::save`], rejecting it
+    /// if it fails [`Blockchain::is_valid`].
+    pub fn load(path: impl AsRef<Path>) -> Result<Self, PersistenceError> {
+        let json = std::fs::read_to_string(path)?;
+        let bc: Blockchain = serde_json::from_str(&json)?;
+        bc.is_valid().map_err(PersistenceError::Invalid)?;
+        Ok(bc)
+    }
+}
+
+/// Why a chain could not be saved to or loaded from disk.
+#[derive(Debug)]
+pub enum PersistenceError {
+    Io(std::io::Error),
+    Serde(serde_json::Error),
+    Invalid(ValidationError),
+}
+
+impl std::fmt::Display for PersistenceError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PersistenceError::Io(e) => write!(f, "persistence i/o error: {}", e),
+            PersistenceError::Serde(e) => write!(f, "persistence serialization error: {}", e),
+            PersistenceError::Invalid(e) => write!(f, "loaded chain failed validation: {}", e),
+        }
+    }
+}
+
+impl std::error::Error for PersistenceError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            PersistenceError::Io(e) => Some(e),
+            PersistenceError::Serde(e) => Some(e),
+            PersistenceError::Invalid(e) => Some(e),
+        }
+    }
+}
+
+impl From<std::io::Error> for PersistenceError {
+    fn from(e: std::io::Error) -> Self {
+        PersistenceError::Io(e)
+    }
+}
+
+impl From<serde_json::Error> for PersistenceError {
+    fn from(e: serde_json::Error) -> Self {
+        PersistenceError::Serde(e)
+    }
+}
+
+/// The specific invariant a block in the chain violated, identified by that
+/// block's index.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ValidationError {
+    /// `block.previous` does not match the id of the preceding block.
+    BrokenLink { index: u32 },
+    /// `block.index` does not follow the preceding block's index.
+    IndexMismatch { index: u32 },
+    /// `block.timestamp` precedes the preceding block's timestamp.
+    NonMonotonicTimestamp { index: u32 },
+    /// `block.difficulty` is lower than the preceding block's difficulty.
+    DifficultyDecreased { index: u32 },
+    /// Recomputing the block id from its transactions/merkle root/nonce does
+    /// not reproduce the stored id.
+    HashMismatch { index: u32 },
+    /// The block id does not have the leading zero bits its stored
+    /// `difficulty` requires.
+    InsufficientWork { index: u32 },
+    /// A transaction in the block has a signature that does not validate
+    /// against its claimed sender.
+    InvalidSignature { index: u32 },
+}
+
+impl std::fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ValidationError::BrokenLink { index } => {
+                write!(f, "block {} does not link to its predecessor", index)
+            }
+            ValidationError::IndexMismatch { index } => {
+                write!(f, "block {} has an out-of-sequence index", index)
+            }
+            ValidationError::NonMonotonicTimestamp { index } => {
+                write!(f, "block {} has a timestamp before its predecessor", index)
+            }
+            ValidationError::DifficultyDecreased { index } => {
+                write!(f, "block {} has a lower difficulty than its predecessor", index)
+            }
+            ValidationError::HashMismatch { index } => {
+                write!(f, "block {} id does not match its recomputed hash", index)
+            }
+            ValidationError::InsufficientWork { index } => {
+                write!(f, "block {} does not meet its proof-of-work difficulty", index)
+            }
+            ValidationError::InvalidSignature { index } => {
+                write!(f, "block {} contains a transaction with an invalid signature", index)
+            }
+        }
     }
 }
 
-#[derive(Debug, Clone)]
+impl std::error::Error for ValidationError {}
+
+/// Why a transaction was rejected on submission.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TxError {
+    /// The transaction's signature does not validate against its claimed
+    /// sender (`from`).
+    InvalidSignature,
+    /// A pending transfer would spend more than the sender's current balance.
+    InsufficientBalance,
+    /// A transfer's amount is not strictly positive.
+    InvalidAmount,
+    /// A sender's pending transfers sum to more than an `i64` can hold.
+    AmountOverflow,
+}
+
+impl std::fmt::Display for TxError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TxError::InvalidSignature => write!(f, "transaction signature does not validate"),
+            TxError::InsufficientBalance => {
+                write!(f, "transfer would spend more than the sender's balance")
+            }
+            TxError::InvalidAmount => write!(f, "transfer amount must be positive"),
+            TxError::AmountOverflow => {
+                write!(f, "sender's pending transfers overflow a 64-bit amount")
+            }
+        }
+    }
+}
+
+impl std::error::Error for TxError {}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Block {
     pub id: String,
     pub previous: String,
     pub index: u32,
-    pub timestamp: u64,
+    pub timestamp: Timestamp,
     pub transactions: Vec<Transaction>,
+    pub merkle_root: String,
+    pub nonce: u64,
+    pub difficulty: usize,
 }
 
 impl Block {
-    fn genesis() -> Self {
+    fn genesis(balances: &[([u8; 32], i64)]) -> Self {
+        let transactions: Vec<Transaction> = balances
+            .iter()
+            .enumerate()
+            .map(|(nonce, (to, amount))| {
+                Transaction::minted(
+                    Payload::Transfer {
+                        to: *to,
+                        amount: *amount,
+                    },
+                    nonce as u32,
+                )
+            })
+            .collect();
+        let tx_ids: Vec<String> = transactions.iter().map(|tx| tx.id.clone()).collect();
+        let merkle_root = utils::merkle_root(&tx_ids);
+        let id_str = merkle_root.clone() + "genesis";
+
         Block {
-            id: utils::hash_str(b"genesis"),
+            id: utils::hash_str(id_str.as_bytes()),
             previous: "".to_string(),
             index: 0,
-            timestamp: utils::current_time(),
-            transactions: vec![],
+            timestamp: Timestamp::now(),
+            merkle_root,
+            transactions,
+            nonce: 0,
+            difficulty: 0,
         }
     }
 
-    fn new(previous: &Block, transactions: Vec<Transaction>) -> Self {
-        let mut id_str = transactions
-            .iter()
-            .map(|tx| tx.id.clone())
-            .fold("".to_string(), |cur, next| cur + &next);
-        id_str += &previous.id;
-        let id = utils::hash_str(id_str.as_bytes());
+    fn new(previous: &Block, transactions: Vec<Transaction>, difficulty: usize) -> Self {
+        let tx_ids: Vec<String> = transactions.iter().map(|tx| tx.id.clone()).collect();
+        let merkle_root = utils::merkle_root(&tx_ids);
+
+        let id_str = merkle_root.clone() + &previous.id + &difficulty.to_string();
+        let (id, nonce) = utils::mine(&id_str, difficulty);
 
         Block {
-            id: id,
+            id,
             previous: previous.id.clone(),
             index: previous.index + 1,
-            timestamp: utils::current_time(),
+            timestamp: Timestamp::now(),
             transactions,
+            merkle_root,
+            nonce,
+            difficulty,
         }
     }
+
+    /// Returns the sibling hashes along the path from `tx_id`'s leaf to the
+    /// merkle root, or `None` if no transaction in this block has that id.
+    pub fn merkle_proof(&self, tx_id: &str) -> Option<Vec<String>> {
+        let index = self.transactions.iter().position(|tx| tx.id == tx_id)?;
+        let tx_ids: Vec<String> = self.transactions.iter().map(|tx| tx.id.clone()).collect();
+        Some(utils::merkle_proof(&tx_ids, index))
+    }
 }
 
-#[derive(Clone, Debug)]
+/// Recomputes a merkle root from a leaf transaction id, its index, and the
+/// sibling hashes returned by [`Block::merkle_proof`], and checks it matches `root`.
+pub fn verify_merkle_proof(tx_id: &str, index: usize, proof: &[String], root: &str) -> bool {
+    utils::verify_merkle_proof(tx_id, index, proof, root)
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct Transaction {
     pub id: String,
-    pub payload: String,
+    pub payload: Payload,
     pub nonce: u32,
-    pub timestamp: u64,
+    pub timestamp: Timestamp,
+    pub from: [u8; 32],
+    #[serde(with = "BigArray")]
+    pub signature: [u8; 64],
 }
 
 impl Transaction {
-    fn new(payload: String, nonce: u32) -> Self {
+    /// The id a transaction with this payload and nonce must have, regardless
+    /// of how it was constructed — used both to mint new ids and to check a
+    /// stored id hasn't been tampered with independently of its payload.
+    fn compute_id(payload: &Payload, nonce: u32) -> String {
         let id_str = format!("{}{}", payload, nonce);
-        let id = utils::hash_str(id_str.as_bytes());
+        utils::hash_str(id_str.as_bytes())
+    }
+
+    fn new(payload: Payload, nonce: u32, signing_key: &SigningKey) -> Self {
+        let timestamp = Timestamp::now();
+        let preimage = utils::tx_preimage(&payload.to_string(), nonce, timestamp.as_secs());
+        let signature = signing_key.sign(preimage.as_bytes());
+        let id = Self::compute_id(&payload, nonce);
+
+        Transaction {
+            id,
+            payload,
+            nonce,
+            timestamp,
+            from: signing_key.verifying_key().to_bytes(),
+            signature: signature.to_bytes(),
+        }
+    }
+
+    /// Builds an unsigned transaction credited to the genesis block, which is
+    /// never passed through [`Transaction::verify`] by [`Blockchain::is_valid`].
+    fn minted(payload: Payload, nonce: u32) -> Self {
+        let id = Self::compute_id(&payload, nonce);
+
         Transaction {
-            id: id,
-            payload: payload,
-            nonce: nonce,
-            timestamp: utils::current_time(),
+            id,
+            payload,
+            nonce,
+            timestamp: Timestamp::now(),
+            from: [0u8; 32],
+            signature: [0u8; 64],
+        }
+    }
+
+    /// Checks that `signature` was produced by the holder of `from`'s private
+    /// key over this transaction's canonical preimage.
+    pub fn verify(&self) -> bool {
+        let Ok(verifying_key) = VerifyingKey::from_bytes(&self.from) else {
+            return false;
+        };
+        let signature = Signature::from_bytes(&self.signature);
+        let preimage = utils::tx_preimage(&self.payload.to_string(), self.nonce, self.timestamp.as_secs());
+        verifying_key.verify(preimage.as_bytes(), &signature).is_ok()
+    }
+}
+
+/// What a transaction does: carry free-form data, or move value between
+/// accounts (identified by their ed25519 public key).
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Payload {
+    Raw(String),
+    Transfer { to: [u8; 32], amount: i64 },
+}
+
+impl std::fmt::Display for Payload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Payload::Raw(s) => write!(f, "{}", s),
+            Payload::Transfer { to, amount } => {
+                write!(f, "transfer:{}:{}", utils::hex_string(to), amount)
+            }
         }
     }
 }
 
+/// A Unix-epoch second count, kept distinct from a bare `u64` so the storage
+/// representation can't be confused with an arbitrary integer and so history
+/// output can render as a readable date rather than a raw second count.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+pub struct Timestamp(u64);
+
+impl Timestamp {
+    pub fn now() -> Self {
+        Timestamp(utils::current_time())
+    }
+
+    pub(crate) fn as_secs(&self) -> u64 {
+        self.0
+    }
+
+    /// Renders this timestamp as an RFC-3339 UTC string, e.g.
+    /// `2024-01-02T03:04:05+00:00`, or a placeholder if the raw second count
+    /// falls outside the range chrono can represent as a `DateTime`.
+    pub fn standard_format(&self) -> String {
+        chrono::DateTime::<chrono::Utc>::from_timestamp(self.0 as i64, 0)
+            .map(|dt| dt.to_rfc3339())
+            .unwrap_or_else(|| format!("<out-of-range timestamp {}>", self.0))
+    }
+}
+
+impl std::ops::Add<u64> for Timestamp {
+    type Output = Timestamp;
+
+    fn add(self, rhs: u64) -> Timestamp {
+        Timestamp(self.0 + rhs)
+    }
+}
+
+impl std::ops::Sub for Timestamp {
+    type Output = i64;
+
+    fn sub(self, rhs: Timestamp) -> i64 {
+        self.0 as i64 - rhs.0 as i64
+    }
+}
+
+impl std::fmt::Display for Timestamp {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.standard_format())
+    }
+}
+
 mod utils {
     use sha2::{Digest, Sha256};
     use std::time::SystemTime;
@@ -108,19 +542,149 @@ mod utils {
         let result = hasher.finalize();
         format!("{:x}", result)
     }
+
+    /// The canonical byte sequence a transaction's signature is computed over.
+    pub(crate) fn tx_preimage(payload: &str, nonce: u32, timestamp: u64) -> String {
+        format!("{}{}{}", payload, nonce, timestamp)
+    }
+
+    pub(crate) fn hash_bytes(bytes: &[u8]) -> Vec<u8> {
+        let mut hasher = Sha256::new();
+        hasher.update(bytes);
+        hasher.finalize().to_vec()
+    }
+
+    pub(crate) fn leading_zero_bits(bytes: &[u8]) -> usize {
+        let mut count = 0;
+        for byte in bytes {
+            if *byte == 0 {
+                count += 8;
+                continue;
+            }
+            count += byte.leading_zeros() as usize;
+            break;
+        }
+        count
+    }
+
+    /// Repeatedly hashes `preimage + nonce` until the digest has at least
+    /// `difficulty` leading zero bits, returning the winning hex digest and nonce.
+    pub(crate) fn mine(preimage: &str, difficulty: usize) -> (String, u64) {
+        let mut nonce: u64 = 0;
+        loop {
+            let digest = hash_with_nonce(preimage, nonce);
+            if leading_zero_bits(&digest) >= difficulty {
+                return (hex_string(&digest), nonce);
+            }
+            nonce += 1;
+        }
+    }
+
+    /// Recomputes the digest a block's id was mined from, for re-verifying
+    /// proof-of-work without redoing the search.
+    pub(crate) fn hash_with_nonce(preimage: &str, nonce: u64) -> Vec<u8> {
+        hash_bytes(format!("{}{}", preimage, nonce).as_bytes())
+    }
+
+    pub(crate) fn hex_string(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    /// Builds a merkle tree over `tx_ids`, hashing each id into a leaf and
+    /// repeatedly hashing pairs of adjacent nodes (duplicating the last node
+    /// when a level has an odd count) until a single root remains.
+    pub(crate) fn merkle_root(tx_ids: &[String]) -> String {
+        if tx_ids.is_empty() {
+            return hash_str(b"");
+        }
+
+        let mut level: Vec<String> = tx_ids.iter().map(|id| hash_str(id.as_bytes())).collect();
+        while level.len() > 1 {
+            level = merkle_level_up(&level);
+        }
+        level.into_iter().next().unwrap()
+    }
+
+    /// Returns the sibling hash at each level on the path from `index` up to
+    /// the root of the tree built over `tx_ids`.
+    pub(crate) fn merkle_proof(tx_ids: &[String], mut index: usize) -> Vec<String> {
+        let mut level: Vec<String> = tx_ids.iter().map(|id| hash_str(id.as_bytes())).collect();
+        let mut proof = Vec::new();
+
+        while level.len() > 1 {
+            let sibling = if index.is_multiple_of(2) { index + 1 } else { index - 1 };
+            let sibling_hash = if sibling < level.len() {
+                level[sibling].clone()
+            } else {
+                level[level.len() - 1].clone()
+            };
+            proof.push(sibling_hash);
+
+            level = merkle_level_up(&level);
+            index /= 2;
+        }
+
+        proof
+    }
+
+    pub(crate) fn verify_merkle_proof(
+        tx_id: &str,
+        mut index: usize,
+        proof: &[String],
+        root: &str,
+    ) -> bool {
+        let mut current = hash_str(tx_id.as_bytes());
+        for sibling in proof {
+            current = if index.is_multiple_of(2) {
+                hash_str(format!("{}{}", current, sibling).as_bytes())
+            } else {
+                hash_str(format!("{}{}", sibling, current).as_bytes())
+            };
+            index /= 2;
+        }
+        current == root
+    }
+
+    fn merkle_level_up(level: &[String]) -> Vec<String> {
+        let mut padded = level.to_vec();
+        if padded.len() % 2 == 1 {
+            padded.push(padded.last().unwrap().clone());
+        }
+        padded
+            .chunks(2)
+            .map(|pair| hash_str(format!("{}{}", pair[0], pair[1]).as_bytes()))
+            .collect()
+    }
 }
 
+#[cfg(test)]
 mod tests {
     use crate::Blockchain;
     use crate::Transaction;
+    use crate::TxError;
+    use crate::Payload;
+    use crate::ValidationError;
+    use ed25519_dalek::SigningKey;
+
+    fn test_signing_key() -> SigningKey {
+        SigningKey::from_bytes(&[7u8; 32])
+    }
 
     #[test]
     fn test_new_tx() {
-        let tx = Transaction::new("hello".to_string(), 0);
+        let tx = Transaction::new(Payload::Raw("hello".to_string()), 0, &test_signing_key());
         assert_eq!(
             tx.id,
             "5a936ee19a0cf3c70d8cb0006111b7a52f45ec01703e0af8cdc8c6d81ac5850c"
         );
+        assert!(tx.verify());
+    }
+
+    #[test]
+    fn test_tx_verify_rejects_tampered_payload() {
+        let mut tx = Transaction::new(Payload::Raw("hello".to_string()), 0, &test_signing_key());
+        tx.payload = Payload::Raw("goodbye".to_string());
+        assert!(!tx.verify());
     }
 
     #[test]
@@ -131,20 +695,283 @@ mod tests {
         assert_eq!(block.index, 0);
         assert_eq!(
             block.id,
-            "aeebad4a796fcc2e15dc4c6061b45ed9b373f26adfc798ca7d2d8cc58182718e"
+            "311da8f516128af9dfe771a4fb641c0fab333a62f4de17172aa85ac78b89eca2"
+        );
+    }
+
+    #[test]
+    fn test_new_block_meets_difficulty() {
+        let mut bc = Blockchain::new();
+        let key = test_signing_key();
+        bc.submit_tx("apple".to_string(), &key).unwrap();
+        bc.submit_tx("orange".to_string(), &key).unwrap();
+        bc.submit_tx("banana".to_string(), &key).unwrap();
+
+        bc.new_block(8).unwrap();
+
+        let block = bc.current_block();
+        assert_eq!(block.difficulty, 8);
+
+        let id_str = block.merkle_root.clone()
+            + &block.previous
+            + &block.difficulty.to_string()
+            + &block.nonce.to_string();
+        let digest = crate::utils::hash_bytes(id_str.as_bytes());
+        assert!(crate::utils::leading_zero_bits(&digest) >= 8);
+    }
+
+    #[test]
+    fn test_is_valid_rejects_difficulty_lowered_without_remining() {
+        let mut bc = Blockchain::new();
+        let key = test_signing_key();
+        bc.submit_tx("apple".to_string(), &key).unwrap();
+        bc.new_block(8).unwrap();
+
+        bc.chain[1].difficulty = 0;
+
+        assert_eq!(
+            bc.is_valid(),
+            Err(ValidationError::HashMismatch { index: 1 })
+        );
+    }
+
+    #[test]
+    fn test_is_valid_rejects_difficulty_downgrade_even_when_remined() {
+        let mut bc = Blockchain::new();
+        let key = test_signing_key();
+        bc.submit_tx("apple".to_string(), &key).unwrap();
+        bc.new_block(8).unwrap();
+        bc.submit_tx("orange".to_string(), &key).unwrap();
+        bc.new_block(8).unwrap();
+
+        let downgraded = crate::Block::new(&bc.chain[1], bc.chain[2].transactions.clone(), 0);
+        bc.chain[2] = downgraded;
+
+        assert_eq!(
+            bc.is_valid(),
+            Err(ValidationError::DifficultyDecreased { index: 2 })
+        );
+    }
+
+    #[test]
+    fn test_merkle_proof_round_trip() {
+        let mut bc = Blockchain::new();
+        let key = test_signing_key();
+        bc.submit_tx("apple".to_string(), &key).unwrap();
+        bc.submit_tx("orange".to_string(), &key).unwrap();
+        bc.submit_tx("banana".to_string(), &key).unwrap();
+        bc.new_block(0).unwrap();
+
+        let block = bc.current_block();
+        let tx = &block.transactions[1];
+        let proof = block.merkle_proof(&tx.id).unwrap();
+
+        assert!(crate::verify_merkle_proof(
+            &tx.id,
+            1,
+            &proof,
+            &block.merkle_root
+        ));
+        assert!(!crate::verify_merkle_proof(
+            &tx.id,
+            0,
+            &proof,
+            &block.merkle_root
+        ));
+    }
+
+    #[test]
+    fn test_is_valid_accepts_well_formed_chain() {
+        let mut bc = Blockchain::new();
+        let key = test_signing_key();
+        bc.submit_tx("apple".to_string(), &key).unwrap();
+        bc.new_block(8).unwrap();
+        bc.submit_tx("orange".to_string(), &key).unwrap();
+        bc.new_block(8).unwrap();
+
+        assert_eq!(bc.is_valid(), Ok(()));
+    }
+
+    #[test]
+    fn test_is_valid_rejects_tampered_block() {
+        let mut bc = Blockchain::new();
+        let key = test_signing_key();
+        bc.submit_tx("apple".to_string(), &key).unwrap();
+        bc.new_block(4).unwrap();
+
+        bc.chain[1]
+            .transactions
+            .push(Transaction::new(Payload::Raw("forged".to_string()), 999, &key));
+
+        assert_eq!(
+            bc.is_valid(),
+            Err(ValidationError::HashMismatch { index: 1 })
         );
     }
 
     #[test]
-    fn testing() {
+    fn test_submit_tx_rejects_forged_signature() {
+        let mut bc = Blockchain::new();
+        let key = test_signing_key();
+        let mut tx = Transaction::new(Payload::Raw("apple".to_string()), bc.nonce, &key);
+        tx.from = SigningKey::from_bytes(&[9u8; 32]).verifying_key().to_bytes();
+
+        assert_eq!(bc.submit_signed_tx(tx), Err(TxError::InvalidSignature));
+    }
+
+    #[test]
+    fn test_save_and_load_round_trip() {
+        let mut bc = Blockchain::new();
+        let key = test_signing_key();
+        bc.submit_tx("apple".to_string(), &key).unwrap();
+        bc.new_block(4).unwrap();
+
+        let path = std::env::temp_dir().join("lilchain_test_save_and_load_round_trip.json");
+        bc.save(&path).unwrap();
+        let loaded = Blockchain::load(&path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+
+        assert_eq!(loaded.chain.len(), bc.chain.len());
+        assert_eq!(loaded.current_block().id, bc.current_block().id);
+    }
+
+    #[test]
+    fn test_load_rejects_tampered_file() {
         let mut bc = Blockchain::new();
-        bc.submit_tx("apple".to_string());
-        bc.submit_tx("orange".to_string());
-        bc.submit_tx("banana".to_string());
-        println!("before - {:?}", bc);
+        let key = test_signing_key();
+        bc.submit_tx("apple".to_string(), &key).unwrap();
+        bc.new_block(4).unwrap();
+        bc.chain[1]
+            .transactions
+            .push(Transaction::new(Payload::Raw("forged".to_string()), 999, &key));
+
+        let path = std::env::temp_dir().join("lilchain_test_load_rejects_tampered_file.json");
+        bc.save(&path).unwrap();
+        let result = Blockchain::load(&path);
+        std::fs::remove_file(&path).unwrap();
+
+        assert!(matches!(result, Err(crate::PersistenceError::Invalid(_))));
+    }
+
+    #[test]
+    fn test_timestamp_standard_format_and_ordering() {
+        let earlier = crate::Timestamp::now();
+        let later = earlier + 60;
+
+        assert!(later > earlier);
+        assert_eq!(later - earlier, 60);
+        assert!(later.standard_format().contains('T'));
+    }
+
+    #[test]
+    fn test_timestamp_standard_format_handles_out_of_range_seconds() {
+        let out_of_range = crate::Timestamp(u64::MAX / 2);
+        assert!(!out_of_range.standard_format().contains('T'));
+    }
+
+    #[test]
+    fn test_balance_reflects_genesis_mint_and_transfers() {
+        let sender = test_signing_key();
+        let sender_account = sender.verifying_key().to_bytes();
+        let receiver_account = SigningKey::from_bytes(&[8u8; 32]).verifying_key().to_bytes();
+
+        let mut bc = Blockchain::with_genesis_balances(&[(sender_account, 100)]).unwrap();
+        assert_eq!(bc.balance(&sender_account), 100);
+
+        bc.submit_transfer(receiver_account, 40, &sender).unwrap();
+        bc.new_block(0).unwrap();
+
+        assert_eq!(bc.balance(&sender_account), 60);
+        assert_eq!(bc.balance(&receiver_account), 40);
+    }
+
+    #[test]
+    fn test_new_block_rejects_overspend() {
+        let sender = test_signing_key();
+        let sender_account = sender.verifying_key().to_bytes();
+        let receiver_account = SigningKey::from_bytes(&[8u8; 32]).verifying_key().to_bytes();
+
+        let mut bc = Blockchain::with_genesis_balances(&[(sender_account, 10)]).unwrap();
+        bc.submit_transfer(receiver_account, 20, &sender).unwrap();
+
+        assert_eq!(bc.new_block(0), Err(TxError::InsufficientBalance));
+    }
+
+    #[test]
+    fn test_submit_transfer_rejects_non_positive_amount() {
+        let sender = test_signing_key();
+        let receiver_account = SigningKey::from_bytes(&[8u8; 32]).verifying_key().to_bytes();
+
+        let mut bc = Blockchain::with_genesis_balances(&[(sender.verifying_key().to_bytes(), 1000)]).unwrap();
+
+        assert_eq!(
+            bc.submit_transfer(receiver_account, -500, &sender),
+            Err(TxError::InvalidAmount)
+        );
+        assert_eq!(
+            bc.submit_transfer(receiver_account, 0, &sender),
+            Err(TxError::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_new_block_rejects_overflowing_pending_spend() {
+        let sender = test_signing_key();
+        let receiver_account = SigningKey::from_bytes(&[8u8; 32]).verifying_key().to_bytes();
+
+        let mut bc = Blockchain::with_genesis_balances(&[(sender.verifying_key().to_bytes(), i64::MAX)]).unwrap();
+        bc.submit_transfer(receiver_account, i64::MAX, &sender)
+            .unwrap();
+        bc.submit_transfer(receiver_account, i64::MAX, &sender)
+            .unwrap();
+
+        assert_eq!(bc.new_block(0), Err(TxError::AmountOverflow));
+    }
+
+    #[test]
+    fn test_with_genesis_balances_rejects_non_positive_amount() {
+        let account = test_signing_key().verifying_key().to_bytes();
+
+        assert_eq!(
+            Blockchain::with_genesis_balances(&[(account, -500)]).err(),
+            Some(TxError::InvalidAmount)
+        );
+        assert_eq!(
+            Blockchain::with_genesis_balances(&[(account, 0)]).err(),
+            Some(TxError::InvalidAmount)
+        );
+    }
+
+    #[test]
+    fn test_is_valid_rejects_tampered_genesis_balance() {
+        let account = test_signing_key().verifying_key().to_bytes();
+        let mut bc = Blockchain::with_genesis_balances(&[(account, 10)]).unwrap();
+
+        match &mut bc.chain[0].transactions[0].payload {
+            Payload::Transfer { amount, .. } => *amount = 999_999,
+            Payload::Raw(_) => unreachable!(),
+        }
+
+        assert_eq!(bc.is_valid(), Err(ValidationError::HashMismatch { index: 0 }));
+    }
+
+    #[test]
+    fn test_genesis_id_depends_on_minted_balances() {
+        let account = test_signing_key().verifying_key().to_bytes();
+        let empty = Blockchain::new();
+        let funded = Blockchain::with_genesis_balances(&[(account, 10)]).unwrap();
+
+        assert_ne!(empty.current_block().id, funded.current_block().id);
+    }
 
-        bc.new_block();
+    #[test]
+    fn test_is_valid_rejects_forged_genesis_id() {
+        let mut bc = Blockchain::with_genesis_balances(&[(
+            test_signing_key().verifying_key().to_bytes(),
+            10,
+        )]).unwrap();
+        bc.chain[0].id = "forged".to_string();
 
-        println!("after - {:?}", bc);
+        assert_eq!(bc.is_valid(), Err(ValidationError::HashMismatch { index: 0 }));
     }
 }